@@ -1,24 +1,157 @@
 use std::cmp;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 extern crate simple;
-use simple::{Event, Point, Window};
-
-#[derive(Debug, Copy, Clone)]
-struct VirtualPoint(f64, f64, (u8, u8, u8)); // (x,y,color) as doubles
+use simple::{Event, Key, Point, Rect, Window};
 
 #[derive(Debug)]
 struct PhysicalPoint(i32, i32, (u8, u8, u8)); // (x,y,color) as ints
 
-const FRACTAL_ITERATIONS: i32 = 255;
-const FRACTAL_ESCAPE: f64 = 2.0;
-const WORKER_THREADS: u32 = 10;
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ColorMode {
+    Smooth,
+    DistanceEstimate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum FractalMode {
+    Mandelbrot,
+    Julia,
+}
+
+// A scanline band handed to a worker as one unit of work.
+#[derive(Debug, Copy, Clone)]
+struct Tile {
+    id: usize,
+    row_start: i32,
+    row_end: i32,
+}
+
+// Everything a worker needs to render a tile, copied in so it doesn't need
+// to reach back into the (non-`Sync`) `Mandelbrot` that queued it.
+#[derive(Debug, Copy, Clone)]
+struct RenderJob {
+    tile: Tile,
+    xmin: f64,
+    xmax: f64,
+    ymin: f64,
+    ymax: f64,
+    width: i32,
+    height: i32,
+    color_mode: ColorMode,
+    iterations: i32,
+    fractal_mode: FractalMode,
+    julia_c: (f64, f64),
+}
+
+struct TileResult {
+    id: usize,
+    colors: Vec<(u8, u8, u8)>, // row-major, `width` columns per row, for this tile's rows only
+}
+
+// A persistent pool of worker threads fed by a shared job queue, so a frame's
+// tiles get pulled up as fast as each worker frees up instead of everyone
+// waiting on a fixed, evenly-split slice of the image.
+struct WorkerPool {
+    job_tx: mpsc::Sender<RenderJob>,
+    result_rx: mpsc::Receiver<TileResult>,
+    _workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    fn new() -> WorkerPool {
+        let thread_count = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let (job_tx, job_rx) = mpsc::channel::<RenderJob>();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        let (result_tx, result_rx) = mpsc::channel::<TileResult>();
+        let mut workers = Vec::<thread::JoinHandle<()>>::new();
+        for _ in 0..thread_count {
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            let worker = thread::spawn(move || loop {
+                let job = {
+                    let job_rx = job_rx.lock().unwrap();
+                    job_rx.recv()
+                };
+                match job {
+                    Ok(job) => {
+                        let colors = WorkerPool::render_tile(&job);
+                        let _ = result_tx.send(TileResult { id: job.tile.id, colors });
+                    }
+                    Err(_) => break,
+                }
+            });
+            workers.push(worker);
+        }
+        return WorkerPool { job_tx, result_rx, _workers: workers };
+    }
+
+    // Computes colors straight into framebuffer order (row-major, `width` wide) --
+    // no intermediate virtual/physical point round-trip.
+    fn render_tile(job: &RenderJob) -> Vec<(u8, u8, u8)> {
+        let pixel_scale = job.width as f64 / (job.xmax - job.xmin);
+        let delta_x = (job.xmax - job.xmin) / job.width as f64;
+        let delta_y = (job.ymax - job.ymin) / job.height as f64;
+        let mut colors = Vec::<(u8, u8, u8)>::new();
+        for row in job.tile.row_start..job.tile.row_end {
+            let y = job.ymin + row as f64 * delta_y;
+            for col in 0..job.width {
+                let x = job.xmin + col as f64 * delta_x;
+                let color = Mandelbrot::calc_mandelbrot_point(x, y, job.color_mode, pixel_scale, job.iterations, job.fractal_mode, job.julia_c);
+                colors.push(color);
+            }
+        }
+        return colors;
+    }
+
+    // Queues every tile for the current view and waits for them all to come back.
+    fn compute_frame(&self, mandelbrot: &Mandelbrot) -> Vec<TileResult> {
+        for tile in &mandelbrot.tiles {
+            let job = RenderJob {
+                tile: *tile,
+                xmin: mandelbrot.xmin,
+                xmax: mandelbrot.xmax,
+                ymin: mandelbrot.ymin,
+                ymax: mandelbrot.ymax,
+                width: mandelbrot.width,
+                height: mandelbrot.height,
+                color_mode: mandelbrot.color_mode,
+                iterations: mandelbrot.iterations,
+                fractal_mode: mandelbrot.fractal_mode,
+                julia_c: mandelbrot.julia_c,
+            };
+            self.job_tx.send(job).unwrap();
+        }
+        let tile_count = mandelbrot.tiles.len();
+        let mut results: Vec<Option<TileResult>> = (0..tile_count).map(|_| None).collect();
+        for _ in 0..tile_count {
+            let result = self.result_rx.recv().unwrap();
+            let id = result.id;
+            results[id] = Some(result);
+        }
+        return results.into_iter().map(|result| result.unwrap()).collect();
+    }
+}
+
+const DEFAULT_FRACTAL_ITERATIONS: i32 = 255;
+const MIN_FRACTAL_ITERATIONS: i32 = 1;
+const MAX_FRACTAL_ITERATIONS: i32 = i32::MAX / 2;
+const FRACTAL_ESCAPE: f64 = 256.0;
+const TILE_HEIGHT: i32 = 4;
 const WINDOW_HEIGHT: i32 = 800;
 const WINDOW_WIDTH: i32 = 800;
 const INIT_VIRTUAL_GRID_XMIN: f64 = -2.0;
 const INIT_VIRTUAL_GRID_XMAX: f64 = 2.0;
 const INIT_VIRTUAL_GRID_YMIN: f64 = -2.0;
 const INIT_VIRTUAL_GRID_YMAX: f64 = 2.0;
+const PAN_STEP: f64 = 0.1;
+const ZOOM_STEP_IN: f64 = 0.8;
+const ZOOM_STEP_OUT: f64 = 1.25;
+const DEFAULT_JULIA_C: (f64, f64) = (-0.8, 0.156);
+const JULIA_NUDGE_STEP: f64 = 0.01;
+const INTERIOR_COLOR: (u8, u8, u8) = (0, 0, 0);
 
 struct Mandelbrot {
     xmin: f64,
@@ -27,117 +160,159 @@ struct Mandelbrot {
     ymax: f64,
     width: i32,
     height: i32,
+    color_mode: ColorMode,
+    iterations: i32,
+    fractal_mode: FractalMode,
+    julia_c: (f64, f64),
+    worker_pool: WorkerPool,
+    tiles: Vec<Tile>,
+    front_buffer: Vec<(u8, u8, u8)>,
+    back_buffer: Vec<(u8, u8, u8)>,
 }
 
 impl Mandelbrot {
     pub fn new() -> Mandelbrot {
-        Mandelbrot { 
+        let tiles = Mandelbrot::build_tiles(WINDOW_HEIGHT);
+        let buffer_size = (WINDOW_WIDTH * WINDOW_HEIGHT) as usize;
+        Mandelbrot {
             xmin: INIT_VIRTUAL_GRID_XMIN,
             xmax: INIT_VIRTUAL_GRID_XMAX,
             ymin: INIT_VIRTUAL_GRID_YMIN,
             ymax: INIT_VIRTUAL_GRID_YMAX,
             width: WINDOW_WIDTH,
             height: WINDOW_HEIGHT,
+            color_mode: ColorMode::Smooth,
+            iterations: DEFAULT_FRACTAL_ITERATIONS,
+            fractal_mode: FractalMode::Mandelbrot,
+            julia_c: DEFAULT_JULIA_C,
+            worker_pool: WorkerPool::new(),
+            tiles,
+            front_buffer: vec![INTERIOR_COLOR; buffer_size],
+            back_buffer: vec![INTERIOR_COLOR; buffer_size],
+        }
+    }
+
+    // Scanline bands covering the whole image; fixed once since `height` never changes.
+    fn build_tiles(height: i32) -> Vec<Tile> {
+        let mut tiles = Vec::<Tile>::new();
+        let mut row = 0;
+        let mut id = 0;
+        while row < height {
+            let row_end = if row + TILE_HEIGHT < height { row + TILE_HEIGHT } else { height };
+            tiles.push(Tile { id, row_start: row, row_end });
+            id += 1;
+            row = row_end;
         }
+        return tiles;
     }
 
-    fn calc_mandelbrot_point(coord: &VirtualPoint) -> Option<VirtualPoint> {
-        let mut prev_x: f64 = coord.0;
-        let mut prev_y: f64 = coord.1;
+    fn calc_mandelbrot_point(
+        x0: f64,
+        y0: f64,
+        mode: ColorMode,
+        pixel_scale: f64,
+        iterations: i32,
+        fractal_mode: FractalMode,
+        julia_c: (f64, f64),
+    ) -> (u8, u8, u8) {
+        // Mandelbrot: z0 = c = pixel. Julia: z0 = pixel, c fixed at `julia_c`.
+        let (c_x, c_y) = match fractal_mode {
+            FractalMode::Mandelbrot => (x0, y0),
+            FractalMode::Julia => julia_c,
+        };
+        let mut prev_x: f64 = x0;
+        let mut prev_y: f64 = y0;
+        let mut dz_x: f64 = 0.0;
+        let mut dz_y: f64 = 0.0;
         let mut escape_count: i32 = 0;
-        for count in 0..FRACTAL_ITERATIONS {
+        let mut dist: f64 = 0.0;
+        let mut escaped = false;
+        for count in 0..iterations {
            escape_count = count;
-           let x = (prev_x * prev_x) - (prev_y * prev_y) + coord.0;
-           let y =  (2.0 * (prev_x * prev_y)) + coord.1;
-           let dist = (x * x + y * y).sqrt();
+           let x = (prev_x * prev_x) - (prev_y * prev_y) + c_x;
+           let y =  (2.0 * (prev_x * prev_y)) + c_y;
+           // dz = 2*z*dz + 1, carried alongside z so the distance estimate can use it on escape.
+           let new_dz_x = 2.0 * (prev_x * dz_x - prev_y * dz_y) + 1.0;
+           let new_dz_y = 2.0 * (prev_x * dz_y + prev_y * dz_x);
+           dz_x = new_dz_x;
+           dz_y = new_dz_y;
+           dist = (x * x + y * y).sqrt();
            prev_x = x;
            prev_y = y;
            if dist > FRACTAL_ESCAPE {
+               escaped = true;
                break;
-           } 
+           }
         }
-        if escape_count > 0 && escape_count < FRACTAL_ITERATIONS-1 {
-            let color = (cmp::min(255, escape_count * 10) as u8, escape_count as u8, escape_count as u8);
-            return Some(VirtualPoint(coord.0, coord.1, color));
+        if !escaped {
+            return INTERIOR_COLOR;
         }
-        return None;
-    }
-
-    fn calc_mandelbrot_worker(points: Vec<VirtualPoint>) -> Vec<VirtualPoint> {
-        //println!("Worker Start Size: {}",points.len());
-        let mut results = Vec::<VirtualPoint>::new();
-        for point in points {
-            let result = Mandelbrot::calc_mandelbrot_point(&point);
-            match result {
-                Some(calc_point) => results.push(calc_point),
-                None => ()
+        let color = match mode {
+            ColorMode::Smooth => {
+                // A couple more iterations after escape so the log-of-log in `mu` settles down.
+                for _ in 0..2 {
+                    let x = (prev_x * prev_x) - (prev_y * prev_y) + c_x;
+                    let y = (2.0 * (prev_x * prev_y)) + c_y;
+                    prev_x = x;
+                    prev_y = y;
+                    dist = (x * x + y * y).sqrt();
+                }
+                let mu = (escape_count as f64 + 1.0 - (dist.ln().ln() / 2f64.ln()))
+                    .max(0.0)
+                    .min(iterations as f64);
+                Mandelbrot::smooth_color(mu, iterations)
             }
-        }
-        //println!("Worker Stop Size: {}",results.len());
-        return results;
-    }
-
-    fn draw_mandelbrot_init(&self) -> Vec<VirtualPoint> {
-        //println!("0a");
-        let mut points = Vec::<VirtualPoint>::new();
-        let delta_x: f64 = (self.xmax - self.xmin) / self.width as f64;
-        let delta_y: f64 = (self.ymax - self.ymin) / self.height as f64;
-        let mut x: f64 = self.xmin;
-        while x <= self.xmax {
-            let mut y: f64 = self.ymin;
-            while y <= self.ymax {
-                points.push(VirtualPoint(x, y, (0, 0, 0)));
-                y += delta_y;
+            ColorMode::DistanceEstimate => {
+                let dz_mag = (dz_x * dz_x + dz_y * dz_y).sqrt();
+                let de = dist * dist.ln() / dz_mag;
+                let de_pixels = de * pixel_scale;
+                Mandelbrot::distance_color(de_pixels)
             }
-            x += delta_x;
+        };
+        return color;
+    }
+
+    // Cosine palette: each channel ramps through a full cycle offset by a phase,
+    // giving a continuous gradient instead of the stepped bands a raw iteration count produces.
+    fn smooth_color(mu: f64, iterations: i32) -> (u8, u8, u8) {
+        let t = mu / iterations as f64;
+        let tau = 2.0 * std::f64::consts::PI;
+        let r = 0.5 + 0.5 * (tau * (t + 0.00)).cos();
+        let g = 0.5 + 0.5 * (tau * (t + 0.33)).cos();
+        let b = 0.5 + 0.5 * (tau * (t + 0.67)).cos();
+        return ((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8);
+    }
+
+    // Shades points near the set boundary (de < ~1px) toward white, and falls off
+    // everything else with `tanh` so filaments further out fade smoothly to black.
+    fn distance_color(de_pixels: f64) -> (u8, u8, u8) {
+        if de_pixels < 1.0 {
+            return (255, 255, 255);
         }
-        //println!("0b");
-        //println!("  Size={}", points.len());
-        return points;
+        let shade = ((1.0 - de_pixels.tanh()) * 255.0) as u8;
+        return (shade, shade, shade);
     }
 
-    fn draw_mandelbrot_run(&self, points: Vec<VirtualPoint>) -> Vec<VirtualPoint>{
-        //println!("1a");
-        let mut threads = Vec::<thread::JoinHandle<Vec::<VirtualPoint>>>::new();
-        for block in 0..WORKER_THREADS {
-            let start_range = (points.len() / WORKER_THREADS as usize) * block as usize;
-            let end_range = (points.len() / WORKER_THREADS as usize) * (block + 1) as usize;
-            let mut subset = Vec::<VirtualPoint>::new();
-            //println!("1b-{} Size: {}", block, subset.len());
-            for index in start_range..end_range {
-                subset.push(points[index]);
-            }
-            let worker = thread::spawn(|| {
-                let result = Mandelbrot::calc_mandelbrot_worker(subset);
-                result
-            });
-            threads.push(worker);
-        }
-        //println!("1b");
-        let mut results = Vec::<VirtualPoint>::new();
-        for thread_handle in threads {
-            let result = thread_handle.join().unwrap();
-            results.extend(result);
-        }
-        //println!("1c");
-        //println!("   Size={}",results.len());
-        return results;
-    }
-
-    fn draw_mandelbrot(&self) -> Vec<PhysicalPoint> {
-        let points = self.draw_mandelbrot_init();
-        let calc_points = self.draw_mandelbrot_run(points);
-        //println!("2a");
-        let mut drawing = Vec::<PhysicalPoint>::new();
-        for point in calc_points {
-            let x = ((point.0 - self.xmin) / (self.xmax - self.xmin) * self.width as f64) as i32;
-            let y = ((point.1 - self.ymin) / (self.ymax - self.ymin) * self.height as f64) as i32;
-            let color = point.2;
-            let drawing_point = PhysicalPoint(x, y, color);
-            drawing.push(drawing_point);
+    // Renders the frame into the back-buffer, swaps it in as the new front-buffer, and
+    // returns every pixel so the caller fully recomposites the canvas. `simple`'s own
+    // examples always `clear()` and redraw the whole frame, which is the idiom you'd
+    // expect if the canvas underneath is a double/triple-buffered swapchain rather than
+    // a single stable buffer -- so skipping unchanged pixels isn't safe to assume here.
+    fn draw_mandelbrot(&mut self) -> Vec<PhysicalPoint> {
+        let tile_results = self.worker_pool.compute_frame(self);
+        for result in tile_results {
+            let tile = self.tiles[result.id];
+            let offset = (tile.row_start * self.width) as usize;
+            self.back_buffer[offset..offset + result.colors.len()].copy_from_slice(&result.colors);
+        }
+        std::mem::swap(&mut self.front_buffer, &mut self.back_buffer);
+        let mut points = Vec::<PhysicalPoint>::with_capacity(self.front_buffer.len());
+        for (i, color) in self.front_buffer.iter().enumerate() {
+            let x = i as i32 % self.width;
+            let y = i as i32 / self.width;
+            points.push(PhysicalPoint(x, y, *color));
         }
-        //println!("2b");
-        return drawing;
+        return points;
     }
 
     fn zoom(&mut self, x:i32, y:i32, ratio:f64) {
@@ -151,14 +326,77 @@ impl Mandelbrot {
         self.ymax = virtual_y + virtual_grid_y_size;
     }
 
+    // Zooms to exactly the virtual region under the screen rectangle (x0,y0)-(x1,y1),
+    // growing the shorter side so the view keeps the window's aspect ratio undistorted.
+    fn zoom_to_rect(&mut self, x0: i32, y0: i32, x1: i32, y1: i32) {
+        let virtual_x0 = (cmp::min(x0, x1) as f64 / self.width as f64) * (self.xmax - self.xmin) + self.xmin;
+        let virtual_x1 = (cmp::max(x0, x1) as f64 / self.width as f64) * (self.xmax - self.xmin) + self.xmin;
+        let virtual_y0 = (cmp::min(y0, y1) as f64 / self.height as f64) * (self.ymax - self.ymin) + self.ymin;
+        let virtual_y1 = (cmp::max(y0, y1) as f64 / self.height as f64) * (self.ymax - self.ymin) + self.ymin;
+        let mut half_width = (virtual_x1 - virtual_x0) / 2.0;
+        let mut half_height = (virtual_y1 - virtual_y0) / 2.0;
+        if half_width < f64::EPSILON || half_height < f64::EPSILON {
+            return; // the user clicked rather than dragged a real rectangle
+        }
+        let center_x = (virtual_x0 + virtual_x1) / 2.0;
+        let center_y = (virtual_y0 + virtual_y1) / 2.0;
+        let aspect = self.width as f64 / self.height as f64;
+        if half_width / half_height > aspect {
+            half_height = half_width / aspect;
+        } else {
+            half_width = half_height * aspect;
+        }
+        self.xmin = center_x - half_width;
+        self.xmax = center_x + half_width;
+        self.ymin = center_y - half_height;
+        self.ymax = center_y + half_height;
+    }
+
+    // Shifts the view by a fraction of the current span, e.g. pan(PAN_STEP, 0.0) nudges right.
+    fn pan(&mut self, dx_frac: f64, dy_frac: f64) {
+        let dx = dx_frac * (self.xmax - self.xmin);
+        let dy = dy_frac * (self.ymax - self.ymin);
+        self.xmin += dx;
+        self.xmax += dx;
+        self.ymin += dy;
+        self.ymax += dy;
+    }
+
+    fn double_iterations(&mut self) {
+        self.iterations = cmp::min(MAX_FRACTAL_ITERATIONS, self.iterations * 2);
+    }
+
+    fn halve_iterations(&mut self) {
+        self.iterations = cmp::max(MIN_FRACTAL_ITERATIONS, self.iterations / 2);
+    }
+
+    fn toggle_color_mode(&mut self) {
+        self.color_mode = match self.color_mode {
+            ColorMode::Smooth => ColorMode::DistanceEstimate,
+            ColorMode::DistanceEstimate => ColorMode::Smooth,
+        };
+    }
+
+    fn toggle_fractal_mode(&mut self) {
+        self.fractal_mode = match self.fractal_mode {
+            FractalMode::Mandelbrot => FractalMode::Julia,
+            FractalMode::Julia => FractalMode::Mandelbrot,
+        };
+    }
+
+    fn nudge_julia_c(&mut self, dre: f64, dim: f64) {
+        self.julia_c = (self.julia_c.0 + dre, self.julia_c.1 + dim);
+    }
+
 }
 
 
 fn main() {
     let mut mandelbrot = Mandelbrot::new();
-    let points = mandelbrot.draw_mandelbrot();    
+    let points = mandelbrot.draw_mandelbrot();
     let mut app = Window::new("Mandelbrot - Rust", 800, 800);
 
+    app.clear();
     //println!("draw now");
     for point in points {
         let color = point.2;
@@ -170,32 +408,94 @@ fn main() {
     //app.set_color(255, 0, 255, 255);
     //app.draw_rect(Rect::new(100, 110, 120, 130));
 
+    let mut dragging = false;
+    let mut drag_start = (0, 0);
+    let mut drag_current = (0, 0);
+
     while app.next_frame() {
         // event handling
         while app.has_event() {
             match app.next_event() {
-                // If the user clicks, we add a new Square at the position of the mouse event.
+                // Mouse-down starts a selection rectangle, mouse-up commits the zoom. `simple`
+                // never queues a motion event, so the drag itself is tracked below by polling
+                // the mouse position once per frame rather than waiting on events.
                 Event::Mouse {
                     is_down: true,
                     mouse_x,
                     mouse_y,
                     ..
-                } => mandelbrot.zoom(mouse_x, mouse_y, 0.8),
+                } => {
+                    if !dragging {
+                        dragging = true;
+                        drag_start = (mouse_x, mouse_y);
+                        drag_current = (mouse_x, mouse_y);
+                    }
+                }
+                Event::Mouse {
+                    is_down: false,
+                    mouse_x,
+                    mouse_y,
+                    ..
+                } => {
+                    if dragging {
+                        dragging = false;
+                        mandelbrot.zoom_to_rect(drag_start.0, drag_start.1, mouse_x, mouse_y);
+                    }
+                }
+
+                // WASD pans, Q/E zooms out/in around the view center, T/G doubles/halves detail.
+                Event::Keyboard { key: Key::W, is_down: true, .. } => mandelbrot.pan(0.0, -PAN_STEP),
+                Event::Keyboard { key: Key::S, is_down: true, .. } => mandelbrot.pan(0.0, PAN_STEP),
+                Event::Keyboard { key: Key::A, is_down: true, .. } => mandelbrot.pan(-PAN_STEP, 0.0),
+                Event::Keyboard { key: Key::D, is_down: true, .. } => mandelbrot.pan(PAN_STEP, 0.0),
+                Event::Keyboard { key: Key::Q, is_down: true, .. } => {
+                    mandelbrot.zoom(mandelbrot.width / 2, mandelbrot.height / 2, ZOOM_STEP_OUT)
+                }
+                Event::Keyboard { key: Key::E, is_down: true, .. } => {
+                    mandelbrot.zoom(mandelbrot.width / 2, mandelbrot.height / 2, ZOOM_STEP_IN)
+                }
+                Event::Keyboard { key: Key::T, is_down: true, .. } => mandelbrot.double_iterations(),
+                Event::Keyboard { key: Key::G, is_down: true, .. } => mandelbrot.halve_iterations(),
+
+                // C switches between smooth escape-time coloring and the distance estimate.
+                Event::Keyboard { key: Key::C, is_down: true, .. } => mandelbrot.toggle_color_mode(),
+
+                // J switches between the Mandelbrot and Julia set, arrow keys nudge Julia's c.
+                Event::Keyboard { key: Key::J, is_down: true, .. } => mandelbrot.toggle_fractal_mode(),
+                Event::Keyboard { key: Key::Left, is_down: true, .. } => mandelbrot.nudge_julia_c(-JULIA_NUDGE_STEP, 0.0),
+                Event::Keyboard { key: Key::Right, is_down: true, .. } => mandelbrot.nudge_julia_c(JULIA_NUDGE_STEP, 0.0),
+                Event::Keyboard { key: Key::Up, is_down: true, .. } => mandelbrot.nudge_julia_c(0.0, JULIA_NUDGE_STEP),
+                Event::Keyboard { key: Key::Down, is_down: true, .. } => mandelbrot.nudge_julia_c(0.0, -JULIA_NUDGE_STEP),
 
                 _ => (),
             }
-            app.clear();
-            let points = mandelbrot.draw_mandelbrot();
-            //println!("draw now");
-            for point in points {
-                let color = point.2;
-                app.set_color(color.0, color.1, color.2, 255);
-                app.draw_point(Point::new(point.0, point.1));
-                //println!("{},{} c={:?}",point.0, point.1, point.2);
-            }
-            //println!("draw done");
         }
 
-       
+        // Polled here rather than from an event: `simple` drops mouse-motion events, so this
+        // is the only way to see the drag update while the button is held.
+        if dragging {
+            drag_current = app.mouse_position();
+        }
+
+        // Every pixel is recomposited each frame -- see the note on `draw_mandelbrot`.
+        app.clear();
+        let points = mandelbrot.draw_mandelbrot();
+        //println!("draw now");
+        for point in points {
+            let color = point.2;
+            app.set_color(color.0, color.1, color.2, 255);
+            app.draw_point(Point::new(point.0, point.1));
+            //println!("{},{} c={:?}",point.0, point.1, point.2);
+        }
+        //println!("draw done");
+        if dragging {
+            // Overlay the selection rectangle so the user can see the target region before committing.
+            app.set_color(255, 255, 0, 180);
+            let rect_x = cmp::min(drag_start.0, drag_current.0);
+            let rect_y = cmp::min(drag_start.1, drag_current.1);
+            let rect_w = (drag_current.0 - drag_start.0).unsigned_abs();
+            let rect_h = (drag_current.1 - drag_start.1).unsigned_abs();
+            app.draw_rect(Rect::new(rect_x, rect_y, rect_w, rect_h));
+        }
     }
 }